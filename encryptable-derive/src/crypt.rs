@@ -0,0 +1,468 @@
+use proc_macro2::{TokenStream, Span};
+use quote::quote;
+use syn::{DeriveInput, Expr, Field, Type, Ident};
+
+#[derive(deluxe::ExtractAttributes)]
+#[deluxe(attributes(encryptable))]
+struct EncryptableAttributes {
+  /// Rust struct that has encrypt and decrypt methods implemented
+  service: Expr,
+  /// Function `fn(key_id: &str, data: &str) -> Vec<u8>` that generates a blind-index
+  /// digest of the supplied value as raw bytes, keyed by `key_id`
+  digest: Option<Expr>,
+  /// Codec used to render the digest's raw bytes to the `_digest` field; defaults to hex.
+  /// Only covers digest output — ciphertext encoding is controlled by the `service`
+  /// itself (e.g. `SymmetricEncryption::with_encoding`).
+  #[deluxe(default = syn::parse_quote!(encryptable::Hex))]
+  digest_encoding: Expr,
+  /// Id of the HMAC key currently in use for digests, carried into every generated
+  /// `#digest(key_id, ...)` call and used to tag the encoded output (`<key_id>:<digest>`)
+  /// so a later key rotation can still verify digests produced under an older key id
+  #[deluxe(default = syn::parse_quote!("v1"))]
+  key_id: Expr,
+  /// Error type returned by the service's fallible `encrypt`/`decrypt` methods
+  #[deluxe(default = syn::parse_quote!(std::io::Error))]
+  error: Type,
+  /// Wrap the plaintext passed to the service in a guard that scrubs its heap buffer on
+  /// drop, for `try_encrypt` only. Note this only protects the owned clone made for the
+  /// call: `try_encrypt` takes `&self`, so the caller's original field is never touched
+  /// and remains readable (and re-encryptable) after the call returns. `try_decrypt` isn't
+  /// covered — it returns plain `String` fields on `Self`, so a guard around the
+  /// intermediate plaintext would just be unwrapped and dropped before it could help.
+  #[deluxe(default)]
+  zeroize: bool,
+  /// Generate an `AsyncCrypt` impl that `.await`s the service's `encrypt`/`decrypt` calls,
+  /// for services backed by a remote key service (KMS, HSM, ...) instead of local crypto
+  #[deluxe(default)]
+  r#async: bool,
+}
+
+#[derive(deluxe::ExtractAttributes, Default)]
+#[deluxe(attributes(encryptable))]
+struct EncryptableFieldAttributes {
+  #[deluxe(default)]
+  encrypt: bool,
+  #[deluxe(default)]
+  decrypt: bool,
+  /// Service to encrypt/decrypt this field with, overriding the struct-level `service`.
+  /// Lets a single record mix, say, a deterministic cipher on indexed columns with a
+  /// randomized AEAD on free-text columns.
+  service: Option<Expr>,
+  /// The field's type itself derives `Encryptable`; recurse into it via its own
+  /// `try_encrypt`/`try_decrypt` rather than calling the service directly.
+  #[deluxe(default)]
+  nested: bool,
+  /// For a `HashMap`/`BTreeMap` field, also encrypt the keys (values are always encrypted).
+  #[deluxe(default)]
+  encrypt_keys: bool,
+  /// Comma-separated field names this digest is computed from, for a composite blind
+  /// index (e.g. `sources = "first_name,last_name"` on a `full_name_digest` field).
+  /// Defaults to the single parent obtained by stripping the `_digest` suffix. Multiple
+  /// sources are length-prefixed before concatenation so e.g. `("a|b", "c")` can't hash
+  /// the same as `("a", "b|c")`.
+  sources: Option<syn::LitStr>,
+}
+
+type EncryptableFieldAttributesDictionary = std::collections::HashMap<Field, EncryptableFieldAttributes>;
+
+fn extract_encryptable_field_attributes(ast: &mut DeriveInput) -> deluxe::Result<EncryptableFieldAttributesDictionary> {
+  let mut field_attributes = EncryptableFieldAttributesDictionary::new();
+
+  if let syn::Data::Struct(structure) = &mut ast.data {
+    for field in structure.fields.iter_mut() {
+      let attributes: EncryptableFieldAttributes = deluxe::extract_attributes(field)?;
+
+      field_attributes.insert(field.to_owned(), attributes);
+    }
+  }
+
+  Ok(field_attributes)
+}
+
+fn is_option(ty: &Type) -> bool {
+  let mut result = false;
+
+  if let Type::Path(type_path) = ty {
+    result = type_path.path.segments.iter().next().unwrap().ident == "Option";
+  }
+
+  result
+}
+
+fn field_service<'a>(attributes: &'a EncryptableFieldAttributes, service: &'a Expr) -> &'a Expr {
+  attributes.service.as_ref().unwrap_or(service)
+}
+
+fn is_vector(ty: &Type) -> bool {
+  let mut result = false;
+
+  if let Type::Path(type_path) = ty {
+    result = type_path.path.segments.iter().next().unwrap().ident == "Vec";
+  }
+
+  result
+}
+
+fn is_map(ty: &Type) -> bool {
+  let mut result = false;
+
+  if let Type::Path(type_path) = ty {
+    let ident = &type_path.path.segments.iter().next().unwrap().ident;
+
+    result = ident == "HashMap" || ident == "BTreeMap";
+  }
+
+  result
+}
+
+fn try_encrypt_field(field: &Field, attributes: &EncryptableFieldAttributes, service: &Expr, zeroize: bool) -> TokenStream {
+  let ident = field.ident.as_ref().unwrap();
+
+  if attributes.nested {
+    return quote! { #ident: self.#ident.try_encrypt()? };
+  }
+
+  if is_map(&field.ty) {
+    let key = if attributes.encrypt_keys && zeroize {
+      quote! { #service.encrypt(&zeroize::Zeroizing::new(key.to_owned()))? }
+    } else if attributes.encrypt_keys {
+      quote! { #service.encrypt(key)? }
+    } else {
+      quote! { key.to_owned() }
+    };
+    let value = if zeroize {
+      quote! { #service.encrypt(&zeroize::Zeroizing::new(value.to_owned()))? }
+    } else {
+      quote! { #service.encrypt(value)? }
+    };
+
+    return quote! {
+      #ident: if self.#ident.is_empty() {
+        self.#ident.to_owned()
+      } else {
+        self.#ident.iter().map(|(key, value)| Ok((#key, #value))).collect::<Result<_, Self::Error>>()?
+      }
+    };
+  }
+
+  if is_option(&field.ty) {
+    let encrypt_value = if zeroize {
+      quote! {
+        let value = zeroize::Zeroizing::new(value.to_owned());
+        Some(#service.encrypt(&value)?)
+      }
+    } else {
+      quote! { Some(#service.encrypt(value)?) }
+    };
+
+    quote! {
+      #ident: if let Some(value) = &self.#ident {
+        if value.is_empty() {
+          Some(value.to_owned())
+        } else {
+          #encrypt_value
+        }
+      } else {
+        None
+      }
+    }
+  } else if is_vector(&field.ty) {
+    let encrypt_value = if zeroize {
+      quote! { #service.encrypt(&zeroize::Zeroizing::new(value.to_owned())) }
+    } else {
+      quote! { #service.encrypt(value) }
+    };
+
+    quote! {
+      #ident: if self.#ident.is_empty() {
+        self.#ident.to_owned()
+      } else {
+        self.#ident.iter().map(|value| #encrypt_value).collect::<Result<Vec<_>, _>>()?
+      }
+    }
+  } else if zeroize {
+    quote! {
+      #ident: if self.#ident.is_empty() {
+        self.#ident.to_owned()
+      } else {
+        let plaintext = zeroize::Zeroizing::new(self.#ident.to_owned());
+
+        #service.encrypt(&plaintext)?
+      }
+    }
+  } else {
+    quote! {
+      #ident: if self.#ident.is_empty() { self.#ident.to_owned() } else { #service.encrypt(&self.#ident)? }
+    }
+  }
+}
+
+fn try_decrypt_field(field: &Field, attributes: &EncryptableFieldAttributes, service: &Expr) -> TokenStream {
+  let ident = field.ident.as_ref().unwrap();
+
+  if attributes.nested {
+    return quote! { #ident: self.#ident.try_decrypt()? };
+  }
+
+  if is_map(&field.ty) {
+    let key = if attributes.encrypt_keys {
+      quote! { #service.decrypt(key)? }
+    } else {
+      quote! { key.to_owned() }
+    };
+
+    return quote! {
+      #ident: if self.#ident.is_empty() {
+        self.#ident.to_owned()
+      } else {
+        self.#ident.iter().map(|(key, value)| Ok((#key, #service.decrypt(value)?))).collect::<Result<_, Self::Error>>()?
+      }
+    };
+  }
+
+  if is_option(&field.ty) {
+    quote! {
+      #ident: if let Some(value) = &self.#ident {
+        if value.is_empty() {
+          Some(value.to_owned())
+        } else {
+          Some(#service.decrypt(value)?)
+        }
+      } else {
+        None
+      }
+    }
+  } else if is_vector(&field.ty) {
+    quote! {
+      #ident: if self.#ident.is_empty() {
+        self.#ident.to_owned()
+      } else {
+        self.#ident.iter().map(|value| #service.decrypt(value)).collect::<Result<Vec<_>, _>>()?
+      }
+    }
+  } else {
+    quote! {
+      #ident: if self.#ident.is_empty() { self.#ident.to_owned() } else { #service.decrypt(&self.#ident)? }
+    }
+  }
+}
+
+fn async_encrypt_field(field: &Field, service: &Expr) -> TokenStream {
+  let ident = field.ident.as_ref().unwrap();
+
+  if is_option(&field.ty) {
+    quote! {
+      #ident: if let Some(value) = &self.#ident {
+        if value.is_empty() {
+          Some(value.to_owned())
+        } else {
+          Some(#service.encrypt(value).await)
+        }
+      } else {
+        None
+      }
+    }
+  } else if is_vector(&field.ty) {
+    quote! {
+      #ident: if self.#ident.is_empty() {
+        self.#ident.to_owned()
+      } else {
+        let mut encrypted = Vec::with_capacity(self.#ident.len());
+
+        for value in self.#ident.iter() {
+          encrypted.push(#service.encrypt(value).await);
+        }
+
+        encrypted
+      }
+    }
+  } else {
+    quote! {
+      #ident: if self.#ident.is_empty() { self.#ident.to_owned() } else { #service.encrypt(&self.#ident).await }
+    }
+  }
+}
+
+fn async_decrypt_field(field: &Field, service: &Expr) -> TokenStream {
+  let ident = field.ident.as_ref().unwrap();
+
+  if is_option(&field.ty) {
+    quote! {
+      #ident: if let Some(value) = &self.#ident {
+        if value.is_empty() {
+          Some(value.to_owned())
+        } else {
+          Some(#service.decrypt(value).await)
+        }
+      } else {
+        None
+      }
+    }
+  } else if is_vector(&field.ty) {
+    quote! {
+      #ident: if self.#ident.is_empty() {
+        self.#ident.to_owned()
+      } else {
+        let mut decrypted = Vec::with_capacity(self.#ident.len());
+
+        for value in self.#ident.iter() {
+          decrypted.push(#service.decrypt(value).await);
+        }
+
+        decrypted
+      }
+    }
+  } else {
+    quote! {
+      #ident: if self.#ident.is_empty() { self.#ident.to_owned() } else { #service.decrypt(&self.#ident).await }
+    }
+  }
+}
+
+fn digest_sources(field_name: &str, attributes: &EncryptableFieldAttributes) -> Vec<Ident> {
+  match &attributes.sources {
+    Some(sources) => sources.value().split(',').map(|source| Ident::new(source.trim(), Span::call_site())).collect(),
+    None => vec![Ident::new(&field_name.replace("_digest", ""), Span::call_site())],
+  }
+}
+
+fn digest_field(field: &Field, attributes: &EncryptableFieldAttributes, func: &Expr, encoding: &Expr, key_id: &Expr) -> TokenStream {
+  let ident = field.ident.as_ref().unwrap();
+  let field_name = ident.to_string();
+  let sources = digest_sources(&field_name, attributes);
+
+  let is_empty = sources.iter().map(|source| quote! { self.#source.is_empty() }).reduce(|left, right| quote! { #left && #right }).unwrap();
+
+  // A bare separator join lets distinct sources collide (`("a|b", "c")` and `("a", "b|c")`
+  // would hash identically), so composite digests length-prefix each source instead. The
+  // single-source default is left as a plain clone so existing (non-composite) digests don't
+  // change format.
+  let combined_input = if sources.len() > 1 {
+    let parts = sources.iter().map(|source| quote! { format!("{}:{}", self.#source.len(), self.#source) });
+
+    quote! { [#(#parts),*].concat() }
+  } else {
+    let source = &sources[0];
+
+    quote! { self.#source.to_owned() }
+  };
+
+  quote! {
+    #ident: if #is_empty {
+      self.#ident.to_owned()
+    } else {
+      format!("{}:{}", #key_id, encryptable::Encoding::encode(&#encoding, &#func(#key_id, &#combined_input)))
+    }
+  }
+}
+
+pub fn encryptable_derive_macro2(item: proc_macro2::TokenStream) -> deluxe::Result<proc_macro2::TokenStream> {
+  let mut ast: DeriveInput = syn::parse2(item)?;
+
+  let EncryptableAttributes { service, digest, digest_encoding, key_id, error, zeroize, r#async } = deluxe::extract_attributes(&mut ast)?;
+  let field_attributes = extract_encryptable_field_attributes(&mut ast)?;
+
+  // define impl variables
+  let ident = &ast.ident;
+  let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+
+  if r#async {
+    let async_encrypt_fields = field_attributes.iter().map(|(field, attributes)| {
+      let field_ident = field.ident.as_ref().unwrap();
+      let field_name = field_ident.to_string();
+
+      if attributes.encrypt {
+        async_encrypt_field(field, field_service(attributes, &service))
+      } else if field_name.ends_with("digest") && digest.is_some() {
+        digest_field(field, attributes, digest.as_ref().unwrap(), &digest_encoding, &key_id)
+      } else {
+        quote! {
+          #field_ident: self.#field_ident.to_owned()
+        }
+      }
+    });
+    let async_decrypt_fields = field_attributes.iter().map(|(field, attributes)| {
+      let field_ident = field.ident.as_ref().unwrap();
+
+      if attributes.decrypt {
+        async_decrypt_field(field, field_service(attributes, &service))
+      } else {
+        quote! {
+          #field_ident: self.#field_ident.to_owned()
+        }
+      }
+    });
+
+    return Ok(quote! {
+      #[async_trait::async_trait]
+      impl #impl_generics AsyncCrypt for #ident #type_generics #where_clause {
+        async fn encrypt(&self) -> Self {
+          Self {
+            #(#async_encrypt_fields),*
+          }
+        }
+
+        async fn decrypt(&self) -> Self {
+          Self {
+            #(#async_decrypt_fields),*
+          }
+        }
+      }
+    });
+  }
+
+  // Generate syntax tree
+  let try_encrypt_fields = field_attributes.iter().map(|(field, attributes)| {
+    let ident = field.ident.as_ref().unwrap();
+    let field_name = ident.to_string();
+
+    if attributes.encrypt || attributes.nested {
+      try_encrypt_field(field, attributes, field_service(attributes, &service), zeroize)
+    } else if field_name.ends_with("digest") && digest.is_some() {
+      digest_field(field, attributes, digest.as_ref().unwrap(), &digest_encoding, &key_id)
+    } else {
+      quote! {
+        #ident: self.#ident.to_owned()
+      }
+    }
+  });
+  let try_decrypt_fields = field_attributes.iter().map(|(field, attributes)| {
+    let ident = field.ident.as_ref().unwrap();
+
+    if attributes.decrypt || attributes.nested {
+      try_decrypt_field(field, attributes, field_service(attributes, &service))
+    } else {
+      quote! {
+        #ident: self.#ident.to_owned()
+      }
+    }
+  });
+
+  Ok(quote! {
+    impl #impl_generics Crypt for #ident #type_generics #where_clause {
+      fn encrypt(&self) -> Self {
+        self.try_encrypt().expect("failed to encrypt")
+      }
+
+      fn decrypt(&self) -> Self {
+        self.try_decrypt().expect("failed to decrypt")
+      }
+    }
+
+    impl #impl_generics TryCrypt for #ident #type_generics #where_clause {
+      type Error = #error;
+
+      fn try_encrypt(&self) -> Result<Self, Self::Error> {
+       Ok(Self {
+        #(#try_encrypt_fields),*
+       })
+      }
+
+      fn try_decrypt(&self) -> Result<Self, Self::Error> {
+       Ok(Self {
+        #(#try_decrypt_fields),*
+       })
+      }
+    }
+  })
+}
@@ -0,0 +1,34 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug)]
+pub struct DigestError(String);
+
+impl std::fmt::Display for DigestError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl std::error::Error for DigestError {}
+
+/// Looks up the HMAC key active for a given `key_id`, so a blind-index digest can be
+/// produced (and an already-stored one verified) across a key-rotation window.
+pub trait DigestKeyring {
+  fn key(&self, key_id: &str) -> Option<&str>;
+}
+
+/// Computes a raw HMAC-SHA256 blind-index digest of `data` under the key registered for
+/// `key_id`. The caller is responsible for tagging the encoded output with `key_id`
+/// (see the `key_id` struct attribute on [`crate::Encryptable`]) so a later key rotation
+/// can tell which key signed an already-stored digest.
+pub fn data_digest(keyring: &dyn DigestKeyring, key_id: &str, data: &str) -> Result<Vec<u8>, DigestError> {
+  let key = keyring.key(key_id).ok_or_else(|| DigestError(format!("no key registered for key id '{key_id}'")))?;
+  let mut hash = HmacSha256::new_from_slice(key.as_bytes()).map_err(|err| DigestError(err.to_string()))?;
+
+  hash.update(data.as_bytes());
+
+  Ok(hash.finalize().into_bytes().to_vec())
+}
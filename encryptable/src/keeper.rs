@@ -0,0 +1,67 @@
+use aes_gcm::aead::{Aead, OsRng, rand_core::RngCore};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+
+use crate::encoding::{decode_tagged, tag_encode, Encoding, Hex};
+
+const NONCE_LEN: usize = 12;
+
+/// Implemented by types that can turn plaintext into ciphertext (and back) for a
+/// [`crate::Encryptable`] derive to call into.
+pub trait CryptKeeper {
+  fn encrypt(&self, plaintext: &str) -> Result<String, std::io::Error>;
+  fn decrypt(&self, ciphertext: &str) -> Result<String, std::io::Error>;
+}
+
+/// AES-256-GCM backed [`CryptKeeper`]. Each call to [`CryptKeeper::encrypt`] draws a fresh
+/// random nonce, renders `nonce || ciphertext || tag` through the configured [`Encoding`]
+/// (hex by default) and prefixes it with that encoding's tag (see [`crate::encoding::tag_encode`]);
+/// [`CryptKeeper::decrypt`] reads the tag to pick the matching decoder, splits the nonce back
+/// off, and fails if the authentication tag doesn't verify. Untagged ciphertext (written before
+/// tagging existed) is read back as hex, so switching `encoding` doesn't strand old rows.
+pub struct SymmetricEncryption {
+  cipher: Aes256Gcm,
+  encoding: Box<dyn Encoding>,
+}
+
+impl SymmetricEncryption {
+  pub fn new(key: &[u8; 32]) -> Self {
+    Self::with_encoding(key, Hex)
+  }
+
+  pub fn with_encoding(key: &[u8; 32], encoding: impl Encoding + 'static) -> Self {
+    Self { cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)), encoding: Box::new(encoding) }
+  }
+}
+
+impl CryptKeeper for SymmetricEncryption {
+  fn encrypt(&self, plaintext: &str) -> Result<String, std::io::Error> {
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let sealed = self.cipher.encrypt(nonce, plaintext.as_bytes())
+      .map_err(|err| std::io::Error::other(err.to_string()))?;
+
+    let mut payload = nonce_bytes.to_vec();
+    payload.extend_from_slice(&sealed);
+
+    Ok(tag_encode(self.encoding.as_ref(), &payload))
+  }
+
+  fn decrypt(&self, ciphertext: &str) -> Result<String, std::io::Error> {
+    let bytes = decode_tagged(ciphertext)
+      .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+
+    if bytes.len() < NONCE_LEN {
+      return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "ciphertext shorter than nonce"));
+    }
+
+    let (nonce_bytes, sealed) = bytes.split_at(NONCE_LEN);
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    let plaintext = self.cipher.decrypt(nonce, sealed)
+      .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, "failed to verify ciphertext tag"))?;
+
+    String::from_utf8(plaintext).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+  }
+}
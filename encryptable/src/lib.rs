@@ -0,0 +1,8 @@
+mod digest;
+mod encoding;
+mod keeper;
+
+pub use digest::{data_digest, DigestError, DigestKeyring};
+pub use encoding::{Base64, Base85, Encoding, EncodingError, Hex};
+pub use keeper::{CryptKeeper, SymmetricEncryption};
+pub use encryptable_derive::Encryptable;
@@ -0,0 +1,416 @@
+#[cfg(test)]
+mod tests {
+  use encryptable::{data_digest, CryptKeeper, DigestKeyring, Encryptable, SymmetricEncryption};
+
+  const TEST_KEY: [u8; 32] = [7u8; 32];
+
+  fn symmetric_encryption() -> SymmetricEncryption {
+    SymmetricEncryption::new(&TEST_KEY)
+  }
+
+  struct Keyring;
+
+  impl DigestKeyring for Keyring {
+    fn key(&self, key_id: &str) -> Option<&str> {
+      match key_id {
+        "v1" => Some("test_key"),
+        "v2" => Some("rotated_test_key"),
+        _ => None,
+      }
+    }
+  }
+
+   trait Crypt {
+    fn encrypt(&self) -> Self;
+    fn decrypt(&self) -> Self;
+  }
+
+  trait TryCrypt: Sized {
+    type Error;
+
+    fn try_encrypt(&self) -> Result<Self, Self::Error>;
+    fn try_decrypt(&self) -> Result<Self, Self::Error>;
+  }
+
+  #[async_trait::async_trait]
+  trait AsyncCrypt {
+    async fn encrypt(&self) -> Self;
+    async fn decrypt(&self) -> Self;
+  }
+
+  fn digest(key_id: &str, data: &str) -> Vec<u8> {
+    data_digest(&Keyring, key_id, data).expect("Failed to digest data")
+  }
+
+  #[test]
+  fn test_encryptable_derive() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), digest = digest)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      name: String,
+      name_digest: String
+    }
+
+    let payload = TestData { name: "Jake".into(), name_digest: "".into() };
+    let encrypted = payload.encrypt();
+
+    assert_ne!(encrypted.name, payload.name);
+    assert_ne!(encrypted.name_digest, payload.name_digest);
+
+    let decrypted = encrypted.decrypt();
+
+    assert_eq!(decrypted.name, payload.name)
+  }
+
+  #[test]
+  fn test_encryptable_derive_empty_string() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), digest = digest)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      name: String,
+      name_digest: String
+    }
+
+    let payload = TestData { name: "".into(), name_digest: "".into() };
+    let encrypted = payload.encrypt();
+
+    assert_eq!(encrypted.name, payload.name);
+    assert_eq!(encrypted.name_digest, payload.name_digest);
+
+    let decrypted = encrypted.decrypt();
+
+    assert_eq!(decrypted.name, payload.name)
+  }
+
+  #[test]
+  fn test_encryptable_derive_vector() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), digest = digest)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      sessions: Vec<String>,
+    }
+
+    let payload = TestData { sessions: vec!["dances".into(), "shopping".into()] };
+    let encrypted = payload.encrypt();
+
+    assert_eq!(encrypted.sessions.len(), payload.sessions.len());
+    assert_ne!(encrypted.sessions[0], payload.sessions[0]);
+
+    let decrypted = encrypted.decrypt();
+
+    assert_eq!(decrypted.sessions.len(), payload.sessions.len());
+    assert_eq!(decrypted.sessions[0], payload.sessions[0]);
+  }
+
+  #[test]
+  fn test_encryptable_derive_optional() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), digest = digest)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      name: Option<String>,
+    }
+
+    let payload = TestData { name: Some("dances".into()) };
+    let encrypted = payload.encrypt();
+
+    assert!(encrypted.name.is_some());
+    assert_ne!(encrypted.name.as_ref().unwrap(), payload.name.as_ref().unwrap());
+
+    let decrypted = encrypted.decrypt();
+
+    assert!(decrypted.name.is_some());
+    assert_eq!(decrypted.name.as_ref().unwrap(), payload.name.as_ref().unwrap());
+  }
+
+  #[test]
+  fn test_encryptable_derive_try_encrypt() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), digest = digest)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      name: String,
+      name_digest: String
+    }
+
+    let payload = TestData { name: "Jake".into(), name_digest: "".into() };
+    let encrypted = payload.try_encrypt().expect("Failed to encrypt");
+
+    assert_ne!(encrypted.name, payload.name);
+
+    let decrypted = encrypted.try_decrypt().expect("Failed to decrypt");
+
+    assert_eq!(decrypted.name, payload.name)
+  }
+
+  #[test]
+  fn test_encryptable_derive_try_decrypt_surfaces_error() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption())]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      name: String,
+    }
+
+    let corrupted = TestData { name: "not-valid-hex".into() };
+
+    assert!(corrupted.try_decrypt().is_err());
+  }
+
+  #[test]
+  fn test_encryptable_derive_zeroize() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), zeroize)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      password: String,
+    }
+
+    let payload = TestData { password: "hunter2".into() };
+    let encrypted = payload.encrypt();
+
+    assert_ne!(encrypted.password, payload.password);
+
+    let decrypted = encrypted.decrypt();
+
+    assert_eq!(decrypted.password, payload.password)
+  }
+
+  #[test]
+  fn test_encryptable_derive_zeroize_does_not_scrub_borrowed_source() {
+    // `encrypt` takes `&self`, so `zeroize` can only scrub the owned clone it encrypts
+    // from; the caller's original field is untouched and can still be read (or
+    // re-encrypted) afterwards.
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), zeroize)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      password: String,
+    }
+
+    let payload = TestData { password: "hunter2".into() };
+    let first = payload.encrypt();
+    let second = payload.encrypt();
+
+    assert_eq!(payload.password, "hunter2");
+    assert_ne!(first.password, second.password);
+  }
+
+  #[test]
+  fn test_encryptable_derive_digest_encoding() {
+    use encryptable::{Base85, Encoding};
+
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), digest = digest, digest_encoding = Base85)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      name: String,
+      name_digest: String,
+    }
+
+    let payload = TestData { name: "Jake".into(), name_digest: "".into() };
+    let encrypted = payload.encrypt();
+
+    assert_eq!(encrypted.name_digest, format!("v1:{}", Base85.encode(&digest("v1", &payload.name))));
+  }
+
+  #[test]
+  fn test_encryptable_derive_digest_key_rotation() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), digest = digest, key_id = "v2")]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      name: String,
+      name_digest: String,
+    }
+
+    let payload = TestData { name: "Jake".into(), name_digest: "".into() };
+    let encrypted = payload.encrypt();
+
+    assert_eq!(encrypted.name_digest, format!("v2:{}", hex::encode(digest("v2", &payload.name))));
+    assert_ne!(encrypted.name_digest, format!("v1:{}", hex::encode(digest("v1", &payload.name))));
+  }
+
+  #[test]
+  fn test_encryptable_derive_digest_composite_sources() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), digest = digest)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      first_name: String,
+      #[encryptable(encrypt, decrypt)]
+      last_name: String,
+      #[encryptable(sources = "first_name,last_name")]
+      full_name_digest: String,
+    }
+
+    let payload = TestData { first_name: "Jake".into(), last_name: "Peralta".into(), full_name_digest: "".into() };
+    let encrypted = payload.encrypt();
+
+    let expected = format!("v1:{}", hex::encode(digest("v1", "4:Jake7:Peralta")));
+    assert_eq!(encrypted.full_name_digest, expected);
+
+    let other = TestData { first_name: "Amy".into(), last_name: "Peralta".into(), full_name_digest: "".into() };
+    let other_encrypted = other.encrypt();
+
+    assert_ne!(encrypted.full_name_digest, other_encrypted.full_name_digest);
+  }
+
+  #[test]
+  fn test_encryptable_derive_digest_composite_sources_avoids_boundary_collision() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption(), digest = digest)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      first_name: String,
+      #[encryptable(encrypt, decrypt)]
+      last_name: String,
+      #[encryptable(sources = "first_name,last_name")]
+      full_name_digest: String,
+    }
+
+    // A bare `join("|")` would hash "a|b" + "c" identically to "a" + "b|c"; length-prefixing
+    // each source must keep these distinct.
+    let left = TestData { first_name: "a|b".into(), last_name: "c".into(), full_name_digest: "".into() };
+    let right = TestData { first_name: "a".into(), last_name: "b|c".into(), full_name_digest: "".into() };
+
+    assert_ne!(left.encrypt().full_name_digest, right.encrypt().full_name_digest);
+  }
+
+  struct RemoteKeyService;
+
+  impl RemoteKeyService {
+    async fn encrypt(&self, plaintext: &str) -> String {
+      symmetric_encryption().encrypt(plaintext).expect("Failed to encrypt")
+    }
+
+    async fn decrypt(&self, ciphertext: &str) -> String {
+      symmetric_encryption().decrypt(ciphertext).expect("Failed to decrypt")
+    }
+  }
+
+  fn remote_key_service() -> RemoteKeyService {
+    RemoteKeyService
+  }
+
+  #[tokio::test]
+  async fn test_encryptable_derive_async() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = remote_key_service(), async)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      name: String,
+    }
+
+    let payload = TestData { name: "Jake".into() };
+    let encrypted = payload.encrypt().await;
+
+    assert_ne!(encrypted.name, payload.name);
+
+    let decrypted = encrypted.decrypt().await;
+
+    assert_eq!(decrypted.name, payload.name)
+  }
+
+  #[tokio::test]
+  async fn test_encryptable_derive_async_empty_string() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = remote_key_service(), async)]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      name: String,
+    }
+
+    let payload = TestData { name: "".into() };
+    let encrypted = payload.encrypt().await;
+
+    assert_eq!(encrypted.name, payload.name);
+
+    let decrypted = encrypted.decrypt().await;
+
+    assert_eq!(decrypted.name, payload.name)
+  }
+
+  #[test]
+  fn test_encryptable_derive_field_service_override() {
+    const OTHER_KEY: [u8; 32] = [9u8; 32];
+
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption())]
+    struct TestData {
+      #[encryptable(encrypt, decrypt)]
+      name: String,
+      #[encryptable(encrypt, decrypt, service = SymmetricEncryption::new(&OTHER_KEY))]
+      ssn: String,
+    }
+
+    let payload = TestData { name: "Jake".into(), ssn: "123-45-6789".into() };
+    let encrypted = payload.encrypt();
+
+    assert_ne!(encrypted.name, payload.name);
+    assert_ne!(encrypted.ssn, payload.ssn);
+
+    // The field-level service is a different key, so decrypting `ssn` with the
+    // struct-level service would fail to verify the AEAD tag.
+    assert!(symmetric_encryption().decrypt(&encrypted.ssn).is_err());
+
+    let decrypted = encrypted.decrypt();
+
+    assert_eq!(decrypted.name, payload.name);
+    assert_eq!(decrypted.ssn, payload.ssn);
+  }
+
+  #[test]
+  fn test_encryptable_derive_nested() {
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption())]
+    struct Address {
+      #[encryptable(encrypt, decrypt)]
+      street: String,
+    }
+
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption())]
+    struct TestData {
+      #[encryptable(nested)]
+      address: Address,
+    }
+
+    let payload = TestData { address: Address { street: "1 Infinite Loop".into() } };
+    let encrypted = payload.encrypt();
+
+    assert_ne!(encrypted.address.street, payload.address.street);
+
+    let decrypted = encrypted.decrypt();
+
+    assert_eq!(decrypted.address.street, payload.address.street);
+  }
+
+  #[test]
+  fn test_encryptable_derive_map() {
+    use std::collections::HashMap;
+
+    #[derive(Debug, Encryptable)]
+    #[encryptable(service = symmetric_encryption())]
+    struct TestData {
+      #[encryptable(encrypt, decrypt, encrypt_keys)]
+      secrets: HashMap<String, String>,
+    }
+
+    let mut secrets = HashMap::new();
+    secrets.insert("api_key".to_string(), "sk-live-123".to_string());
+
+    let payload = TestData { secrets };
+    let encrypted = payload.encrypt();
+
+    assert_eq!(encrypted.secrets.len(), payload.secrets.len());
+    assert!(!encrypted.secrets.contains_key("api_key"));
+
+    let decrypted = encrypted.decrypt();
+
+    assert_eq!(decrypted.secrets, payload.secrets);
+  }
+}